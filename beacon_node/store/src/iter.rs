@@ -1,13 +1,118 @@
 use crate::Store;
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::sync::Arc;
 use types::{BeaconBlock, BeaconState, BeaconStateError, EthSpec, Hash256, Slot};
 
+use self::state_cache::StateCache;
+
+/// Default number of historical `BeaconState`s kept in a `StateCache` when one isn't supplied
+/// explicitly.
+pub const DEFAULT_STATE_CACHE_SIZE: usize = 4;
+
+/// Metrics for this module, registered with the crate-wide `metrics` module.
+pub mod metrics {
+    use lazy_static::lazy_static;
+    use lighthouse_metrics::*;
+
+    lazy_static! {
+        pub static ref STATE_CACHE_MISSES: Result<IntCounter> = try_create_int_counter(
+            "store_state_cache_misses_total",
+            "Number of StateCache lookups that required reading a BeaconState from the Store"
+        );
+    }
+}
+
+/// A bounded, LRU cache of historical `BeaconState`s, keyed by state root.
+///
+/// Shared (via `Arc<Mutex<_>>`) between clones of a root iterator so that walking long histories,
+/// or running several iterators over the same range concurrently, only deserializes each
+/// historical state from the `Store` once.
+pub mod state_cache {
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use types::{BeaconState, EthSpec, Hash256};
+
+    #[derive(Clone)]
+    pub struct StateCache<T: EthSpec> {
+        inner: Arc<Mutex<Inner<T>>>,
+        misses: Arc<AtomicUsize>,
+    }
+
+    struct Inner<T: EthSpec> {
+        capacity: usize,
+        order: VecDeque<Hash256>,
+        states: HashMap<Hash256, BeaconState<T>>,
+    }
+
+    impl<T: EthSpec> StateCache<T> {
+        /// Create a new cache that holds at most `capacity` states (a `capacity` of zero is
+        /// treated as one), evicting the least-recently-used entry once it is exceeded.
+        pub fn new(capacity: usize) -> Self {
+            Self {
+                inner: Arc::new(Mutex::new(Inner {
+                    capacity: capacity.max(1),
+                    order: VecDeque::new(),
+                    states: HashMap::new(),
+                })),
+                misses: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+
+        /// Returns a clone of the cached state for `state_root`, if present.
+        pub fn get(&self, state_root: &Hash256) -> Option<BeaconState<T>> {
+            let mut inner = self.inner.lock().ok()?;
+
+            let state = inner.states.get(state_root).cloned()?;
+
+            inner.order.retain(|root| root != state_root);
+            inner.order.push_back(*state_root);
+
+            Some(state)
+        }
+
+        /// Insert `state` into the cache under `state_root`, evicting the least-recently-used
+        /// entry if the cache is over capacity.
+        pub fn insert(&self, state_root: Hash256, state: BeaconState<T>) {
+            let mut inner = match self.inner.lock() {
+                Ok(inner) => inner,
+                Err(_) => return,
+            };
+
+            inner.order.retain(|root| *root != state_root);
+            inner.order.push_back(state_root);
+            inner.states.insert(state_root, state);
+
+            while inner.order.len() > inner.capacity {
+                if let Some(oldest) = inner.order.pop_front() {
+                    inner.states.remove(&oldest);
+                }
+            }
+        }
+
+        /// Record a miss in a lookup against this cache that required reading from the `Store`.
+        ///
+        /// Updates both this cache's own counter (so a single iterator's behaviour can be
+        /// asserted on in tests) and the crate-wide `store_state_cache_misses_total` metric.
+        pub fn record_miss(&self) {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            lighthouse_metrics::inc_counter(&super::metrics::STATE_CACHE_MISSES);
+        }
+
+        /// Total number of misses recorded against this cache since it was created.
+        pub fn misses(&self) -> usize {
+            self.misses.load(Ordering::Relaxed)
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct StateRootsIterator<'a, T: EthSpec, U> {
     store: Arc<U>,
     beacon_state: Cow<'a, BeaconState<T>>,
     slot: Slot,
+    cache: Option<StateCache<T>>,
 }
 
 impl<'a, T: EthSpec, U: Store> StateRootsIterator<'a, T, U> {
@@ -17,8 +122,41 @@ impl<'a, T: EthSpec, U: Store> StateRootsIterator<'a, T, U> {
             store,
             beacon_state: Cow::Borrowed(beacon_state),
             slot: start_slot,
+            cache: None,
+        }
+    }
+
+    /// As per `new`, but reuses already-loaded historical states across calls to `next` (and
+    /// across clones of this iterator) via a bounded, shared `StateCache`.
+    pub fn new_with_cache(
+        store: Arc<U>,
+        beacon_state: &'a BeaconState<T>,
+        start_slot: Slot,
+        cache_size: usize,
+    ) -> Self {
+        Self {
+            store,
+            beacon_state: Cow::Borrowed(beacon_state),
+            slot: start_slot,
+            cache: Some(StateCache::new(cache_size)),
         }
     }
+
+    /// As per `new_with_cache`, using `DEFAULT_STATE_CACHE_SIZE` in place of an explicit
+    /// `cache_size`.
+    pub fn new_with_default_cache(
+        store: Arc<U>,
+        beacon_state: &'a BeaconState<T>,
+        start_slot: Slot,
+    ) -> Self {
+        Self::new_with_cache(store, beacon_state, start_slot, DEFAULT_STATE_CACHE_SIZE)
+    }
+
+    /// Total number of `StateCache` misses incurred by this iterator's cache, or zero if it
+    /// wasn't constructed with one.
+    pub fn cache_misses(&self) -> usize {
+        self.cache.as_ref().map_or(0, StateCache::misses)
+    }
 }
 
 impl<'a, T: EthSpec, U: Store> Iterator for StateRootsIterator<'a, T, U> {
@@ -34,12 +172,12 @@ impl<'a, T: EthSpec, U: Store> Iterator for StateRootsIterator<'a, T, U> {
         match self.beacon_state.get_state_root(self.slot) {
             Ok(root) => Some((*root, self.slot)),
             Err(BeaconStateError::SlotOutOfBounds) => {
-                // Read a `BeaconState` from the store that has access to prior historical root.
-                let beacon_state: BeaconState<T> = {
-                    let new_state_root = self.beacon_state.get_oldest_state_root().ok()?;
+                // Read a `BeaconState` from the store that has access to prior historical root,
+                // going via the cache first if one has been configured.
+                let new_state_root = self.beacon_state.get_oldest_state_root().ok()?;
 
-                    self.store.get(&new_state_root).ok()?
-                }?;
+                let beacon_state =
+                    load_historical_state(&self.store, &self.cache, &new_state_root)?;
 
                 self.beacon_state = Cow::Owned(beacon_state);
 
@@ -88,6 +226,7 @@ pub struct BlockRootsIterator<'a, T: EthSpec, U> {
     store: Arc<U>,
     beacon_state: Cow<'a, BeaconState<T>>,
     slot: Slot,
+    cache: Option<StateCache<T>>,
 }
 
 impl<'a, T: EthSpec, U: Store> BlockRootsIterator<'a, T, U> {
@@ -97,8 +236,41 @@ impl<'a, T: EthSpec, U: Store> BlockRootsIterator<'a, T, U> {
             slot: start_slot,
             beacon_state: Cow::Borrowed(beacon_state),
             store,
+            cache: None,
         }
     }
+
+    /// As per `new`, but reuses already-loaded historical states across calls to `next` (and
+    /// across clones of this iterator) via a bounded, shared `StateCache`.
+    pub fn new_with_cache(
+        store: Arc<U>,
+        beacon_state: &'a BeaconState<T>,
+        start_slot: Slot,
+        cache_size: usize,
+    ) -> Self {
+        Self {
+            slot: start_slot,
+            beacon_state: Cow::Borrowed(beacon_state),
+            store,
+            cache: Some(StateCache::new(cache_size)),
+        }
+    }
+
+    /// As per `new_with_cache`, using `DEFAULT_STATE_CACHE_SIZE` in place of an explicit
+    /// `cache_size`.
+    pub fn new_with_default_cache(
+        store: Arc<U>,
+        beacon_state: &'a BeaconState<T>,
+        start_slot: Slot,
+    ) -> Self {
+        Self::new_with_cache(store, beacon_state, start_slot, DEFAULT_STATE_CACHE_SIZE)
+    }
+
+    /// Total number of `StateCache` misses incurred by this iterator's cache, or zero if it
+    /// wasn't constructed with one.
+    pub fn cache_misses(&self) -> usize {
+        self.cache.as_ref().map_or(0, StateCache::misses)
+    }
 }
 
 impl<'a, T: EthSpec, U: Store> Iterator for BlockRootsIterator<'a, T, U> {
@@ -114,14 +286,13 @@ impl<'a, T: EthSpec, U: Store> Iterator for BlockRootsIterator<'a, T, U> {
         match self.beacon_state.get_block_root(self.slot) {
             Ok(root) => Some((*root, self.slot)),
             Err(BeaconStateError::SlotOutOfBounds) => {
-                // Read a `BeaconState` from the store that has access to prior historical root.
-                let beacon_state: BeaconState<T> = {
-                    // Load the earlier state from disk. Skip forward one slot, because a state
-                    // doesn't return it's own state root.
-                    let new_state_root = self.beacon_state.get_oldest_state_root().ok()?;
+                // Read a `BeaconState` from the store that has access to prior historical root,
+                // going via the cache first if one has been configured. Skip forward one slot,
+                // because a state doesn't return it's own state root.
+                let new_state_root = self.beacon_state.get_oldest_state_root().ok()?;
 
-                    self.store.get(&new_state_root).ok()?
-                }?;
+                let beacon_state =
+                    load_historical_state(&self.store, &self.cache, &new_state_root)?;
 
                 self.beacon_state = Cow::Owned(beacon_state);
 
@@ -134,6 +305,371 @@ impl<'a, T: EthSpec, U: Store> Iterator for BlockRootsIterator<'a, T, U> {
     }
 }
 
+/// Load the historical `BeaconState` with root `state_root`, preferring `cache` (when present)
+/// over a fresh `Store` read. Records a miss on `cache` itself whenever `cache` is `Some` and the
+/// lookup isn't already resident.
+fn load_historical_state<T: EthSpec, U: Store>(
+    store: &Arc<U>,
+    cache: &Option<StateCache<T>>,
+    state_root: &Hash256,
+) -> Option<BeaconState<T>> {
+    if let Some(cache) = cache {
+        if let Some(beacon_state) = cache.get(state_root) {
+            return Some(beacon_state);
+        }
+
+        cache.record_miss();
+
+        let beacon_state: BeaconState<T> = store.get(state_root).ok()??;
+        cache.insert(*state_root, beacon_state.clone());
+
+        Some(beacon_state)
+    } else {
+        store.get(state_root).ok()?
+    }
+}
+
+/// Iterates backwards through both block roots and state roots, yielding both from a single
+/// historical `BeaconState` at each slot.
+///
+/// This is equivalent to running `BlockRootsIterator` and `StateRootsIterator` in lockstep, but
+/// each historical `BeaconState` crossed by the walk is only loaded from the `Store` once,
+/// instead of once per iterator.
+#[derive(Clone)]
+pub struct RootsIterator<'a, T: EthSpec, U> {
+    store: Arc<U>,
+    beacon_state: Cow<'a, BeaconState<T>>,
+    slot: Slot,
+    cache: Option<StateCache<T>>,
+}
+
+impl<'a, T: EthSpec, U: Store> RootsIterator<'a, T, U> {
+    /// Create a new iterator over all block and state roots in the given `beacon_state` and
+    /// prior states.
+    pub fn new(store: Arc<U>, beacon_state: &'a BeaconState<T>, start_slot: Slot) -> Self {
+        Self {
+            store,
+            beacon_state: Cow::Borrowed(beacon_state),
+            slot: start_slot,
+            cache: None,
+        }
+    }
+
+    /// As per `new`, but reuses already-loaded historical states across calls to `next` (and
+    /// across clones of this iterator) via a bounded, shared `StateCache`.
+    pub fn new_with_cache(
+        store: Arc<U>,
+        beacon_state: &'a BeaconState<T>,
+        start_slot: Slot,
+        cache_size: usize,
+    ) -> Self {
+        Self {
+            store,
+            beacon_state: Cow::Borrowed(beacon_state),
+            slot: start_slot,
+            cache: Some(StateCache::new(cache_size)),
+        }
+    }
+
+    /// As per `new_with_cache`, using `DEFAULT_STATE_CACHE_SIZE` in place of an explicit
+    /// `cache_size`.
+    pub fn new_with_default_cache(
+        store: Arc<U>,
+        beacon_state: &'a BeaconState<T>,
+        start_slot: Slot,
+    ) -> Self {
+        Self::new_with_cache(store, beacon_state, start_slot, DEFAULT_STATE_CACHE_SIZE)
+    }
+
+    /// Total number of `StateCache` misses incurred by this iterator's cache, or zero if it
+    /// wasn't constructed with one.
+    pub fn cache_misses(&self) -> usize {
+        self.cache.as_ref().map_or(0, StateCache::misses)
+    }
+}
+
+impl<'a, T: EthSpec, U: Store> Iterator for RootsIterator<'a, T, U> {
+    type Item = (Hash256, Hash256, Slot);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if (self.slot == 0) || (self.slot > self.beacon_state.slot) {
+            return None;
+        }
+
+        self.slot -= 1;
+
+        match (
+            self.beacon_state.get_block_root(self.slot),
+            self.beacon_state.get_state_root(self.slot),
+        ) {
+            (Ok(block_root), Ok(state_root)) => Some((*block_root, *state_root, self.slot)),
+            (Err(BeaconStateError::SlotOutOfBounds), _)
+            | (_, Err(BeaconStateError::SlotOutOfBounds)) => {
+                // Read a `BeaconState` from the store that has access to prior historical roots,
+                // going via the cache first if one has been configured. Both roots are then read
+                // from this single loaded state.
+                let new_state_root = self.beacon_state.get_oldest_state_root().ok()?;
+
+                let beacon_state =
+                    load_historical_state(&self.store, &self.cache, &new_state_root)?;
+
+                self.beacon_state = Cow::Owned(beacon_state);
+
+                let block_root = self.beacon_state.get_block_root(self.slot).ok()?;
+                let state_root = self.beacon_state.get_state_root(self.slot).ok()?;
+
+                Some((*block_root, *state_root, self.slot))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Walk backward from `top_state` only as far as necessary to find the historical `BeaconState`
+/// whose own root arrays cover `start_slot`, loading any intermediate states from the `Store`
+/// (via `cache`, if configured) along the way.
+///
+/// Returns that located state together with the newer states above it, in ascending order, for
+/// a forward walk to consume one at a time as it catches up to each one's slot. This touches the
+/// `Store` at most once per historical-root period between `start_slot` and `top_state.slot`,
+/// never once per slot.
+fn locate_ascending_chain<T: EthSpec, U: Store>(
+    store: &Arc<U>,
+    cache: &Option<StateCache<T>>,
+    top_state: BeaconState<T>,
+    start_slot: Slot,
+) -> Option<(BeaconState<T>, VecDeque<BeaconState<T>>)> {
+    let window = Slot::from(T::slots_per_historical_root());
+
+    let mut visited = vec![top_state];
+
+    loop {
+        let oldest = visited.last().expect("just pushed at least one state");
+
+        let lower_bound = if oldest.slot > window {
+            oldest.slot - window
+        } else {
+            Slot::from(0u64)
+        };
+
+        if oldest.slot == 0 || start_slot >= lower_bound {
+            break;
+        }
+
+        let new_state_root = oldest.get_oldest_state_root().ok()?;
+        let older_state = load_historical_state(store, cache, &new_state_root)?;
+
+        visited.push(older_state);
+    }
+
+    visited.reverse();
+
+    let mut chain: VecDeque<BeaconState<T>> = visited.into();
+    let current = chain.pop_front()?;
+
+    Some((current, chain))
+}
+
+/// Iterates forwards through block roots, i.e. in ascending slot order, up to but not including
+/// `beacon_state.slot`.
+///
+/// This is the reverse of `BlockRootsIterator`, which walks backwards towards genesis. Since a
+/// `BeaconState` only stores pointers to the states that came *before* it, the very first call to
+/// `next` has to locate the oldest historical state covering `start_slot` via
+/// `locate_ascending_chain`, touching the `Store` once per historical-root period crossed (not
+/// once per slot). That lookup is deferred until the first `next` call, and every subsequent call
+/// is O(1): it just walks forward through already-loaded states.
+pub struct ReverseBlockRootsIterator<T: EthSpec, U> {
+    store: Arc<U>,
+    cache: Option<StateCache<T>>,
+    top_state: Option<BeaconState<T>>,
+    current: Option<BeaconState<T>>,
+    pending: VecDeque<BeaconState<T>>,
+    slot: Slot,
+    end_slot: Slot,
+}
+
+impl<T: EthSpec, U: Store> ReverseBlockRootsIterator<T, U> {
+    /// Create a new iterator over block roots from `start_slot` up to, but not including,
+    /// `beacon_state.slot`, in ascending order.
+    pub fn new(store: Arc<U>, beacon_state: &BeaconState<T>, start_slot: Slot) -> Self {
+        Self {
+            store,
+            cache: None,
+            top_state: Some(beacon_state.clone()),
+            current: None,
+            pending: VecDeque::new(),
+            slot: start_slot,
+            end_slot: beacon_state.slot,
+        }
+    }
+
+    /// As per `new`, but reuses already-loaded historical states across calls to `next` via a
+    /// bounded, shared `StateCache`.
+    pub fn new_with_cache(
+        store: Arc<U>,
+        beacon_state: &BeaconState<T>,
+        start_slot: Slot,
+        cache_size: usize,
+    ) -> Self {
+        Self {
+            cache: Some(StateCache::new(cache_size)),
+            ..Self::new(store, beacon_state, start_slot)
+        }
+    }
+
+    /// As per `new_with_cache`, using `DEFAULT_STATE_CACHE_SIZE` in place of an explicit
+    /// `cache_size`.
+    pub fn new_with_default_cache(
+        store: Arc<U>,
+        beacon_state: &BeaconState<T>,
+        start_slot: Slot,
+    ) -> Self {
+        Self::new_with_cache(store, beacon_state, start_slot, DEFAULT_STATE_CACHE_SIZE)
+    }
+
+    /// Total number of `StateCache` misses incurred by this iterator's cache, or zero if it
+    /// wasn't constructed with one.
+    pub fn cache_misses(&self) -> usize {
+        self.cache.as_ref().map_or(0, StateCache::misses)
+    }
+}
+
+impl<T: EthSpec, U: Store> Iterator for ReverseBlockRootsIterator<T, U> {
+    type Item = (Hash256, Slot);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slot >= self.end_slot {
+            return None;
+        }
+
+        if let Some(top_state) = self.top_state.take() {
+            let (current, pending) =
+                locate_ascending_chain(&self.store, &self.cache, top_state, self.slot)?;
+            self.current = Some(current);
+            self.pending = pending;
+        }
+
+        loop {
+            let needs_advance = match &self.current {
+                Some(current) => self.slot >= current.slot,
+                None => true,
+            };
+
+            if !needs_advance {
+                break;
+            }
+
+            self.current = Some(self.pending.pop_front()?);
+        }
+
+        let current = self.current.as_ref()?;
+        let root = *current.get_block_root(self.slot).ok()?;
+        let slot = self.slot;
+        self.slot += 1;
+
+        Some((root, slot))
+    }
+}
+
+/// Iterates forwards through state roots, i.e. in ascending slot order, up to but not including
+/// `beacon_state.slot`.
+///
+/// See `ReverseBlockRootsIterator` for details of how the lazy forward walk is built on top of
+/// the backward-only `StateRootsIterator`'s historical links.
+pub struct ReverseStateRootsIterator<T: EthSpec, U> {
+    store: Arc<U>,
+    cache: Option<StateCache<T>>,
+    top_state: Option<BeaconState<T>>,
+    current: Option<BeaconState<T>>,
+    pending: VecDeque<BeaconState<T>>,
+    slot: Slot,
+    end_slot: Slot,
+}
+
+impl<T: EthSpec, U: Store> ReverseStateRootsIterator<T, U> {
+    /// Create a new iterator over state roots from `start_slot` up to, but not including,
+    /// `beacon_state.slot`, in ascending order.
+    pub fn new(store: Arc<U>, beacon_state: &BeaconState<T>, start_slot: Slot) -> Self {
+        Self {
+            store,
+            cache: None,
+            top_state: Some(beacon_state.clone()),
+            current: None,
+            pending: VecDeque::new(),
+            slot: start_slot,
+            end_slot: beacon_state.slot,
+        }
+    }
+
+    /// As per `new`, but reuses already-loaded historical states across calls to `next` via a
+    /// bounded, shared `StateCache`.
+    pub fn new_with_cache(
+        store: Arc<U>,
+        beacon_state: &BeaconState<T>,
+        start_slot: Slot,
+        cache_size: usize,
+    ) -> Self {
+        Self {
+            cache: Some(StateCache::new(cache_size)),
+            ..Self::new(store, beacon_state, start_slot)
+        }
+    }
+
+    /// As per `new_with_cache`, using `DEFAULT_STATE_CACHE_SIZE` in place of an explicit
+    /// `cache_size`.
+    pub fn new_with_default_cache(
+        store: Arc<U>,
+        beacon_state: &BeaconState<T>,
+        start_slot: Slot,
+    ) -> Self {
+        Self::new_with_cache(store, beacon_state, start_slot, DEFAULT_STATE_CACHE_SIZE)
+    }
+
+    /// Total number of `StateCache` misses incurred by this iterator's cache, or zero if it
+    /// wasn't constructed with one.
+    pub fn cache_misses(&self) -> usize {
+        self.cache.as_ref().map_or(0, StateCache::misses)
+    }
+}
+
+impl<T: EthSpec, U: Store> Iterator for ReverseStateRootsIterator<T, U> {
+    type Item = (Hash256, Slot);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slot >= self.end_slot {
+            return None;
+        }
+
+        if let Some(top_state) = self.top_state.take() {
+            let (current, pending) =
+                locate_ascending_chain(&self.store, &self.cache, top_state, self.slot)?;
+            self.current = Some(current);
+            self.pending = pending;
+        }
+
+        loop {
+            let needs_advance = match &self.current {
+                Some(current) => self.slot >= current.slot,
+                None => true,
+            };
+
+            if !needs_advance {
+                break;
+            }
+
+            self.current = Some(self.pending.pop_front()?);
+        }
+
+        let current = self.current.as_ref()?;
+        let root = *current.get_state_root(self.slot).ok()?;
+        let slot = self.slot;
+        self.slot += 1;
+
+        Some((root, slot))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -256,4 +792,182 @@ mod test {
             assert_eq!(hash, Hash256::from(i as u64), "hash mismatch at {}", i);
         }
     }
+
+    #[test]
+    fn reverse_block_root_iter() {
+        let store = Arc::new(MemoryStore::open());
+        let slots_per_historical_root = MainnetEthSpec::slots_per_historical_root();
+
+        let mut state_a: BeaconState<MainnetEthSpec> = get_state();
+        let mut state_b: BeaconState<MainnetEthSpec> = get_state();
+
+        state_a.slot = Slot::from(slots_per_historical_root);
+        state_b.slot = Slot::from(slots_per_historical_root * 2);
+
+        let mut hashes = (0..).into_iter().map(|i| Hash256::from(i));
+
+        for root in &mut state_a.latest_block_roots[..] {
+            *root = hashes.next().unwrap()
+        }
+        for root in &mut state_b.latest_block_roots[..] {
+            *root = hashes.next().unwrap()
+        }
+
+        let state_a_root = hashes.next().unwrap();
+        state_b.latest_state_roots[0] = state_a_root;
+        store.put(&state_a_root, &state_a).unwrap();
+
+        let iter = ReverseBlockRootsIterator::new(store.clone(), &state_b, Slot::from(0u64));
+
+        let collected: Vec<(Hash256, Slot)> = iter.collect();
+
+        // `ReverseBlockRootsIterator` stops one slot short of `beacon_state.slot` (it never
+        // yields `beacon_state.slot` itself), so the walk from genesis covers exactly
+        // `2 * slots_per_historical_root` slots: `0..=(state_b.slot - 1)`.
+        let expected_len = 2 * MainnetEthSpec::slots_per_historical_root();
+
+        assert_eq!(collected.len(), expected_len);
+
+        for (i, (hash, slot)) in collected.into_iter().enumerate() {
+            assert_eq!(slot, Slot::from(i as u64), "slot mismatch at {}", i);
+            assert_eq!(hash, Hash256::from(i as u64), "hash mismatch at {}", i);
+        }
+    }
+
+    #[test]
+    fn reverse_state_root_iter() {
+        let store = Arc::new(MemoryStore::open());
+        let slots_per_historical_root = MainnetEthSpec::slots_per_historical_root();
+
+        let mut state_a: BeaconState<MainnetEthSpec> = get_state();
+        let mut state_b: BeaconState<MainnetEthSpec> = get_state();
+
+        state_a.slot = Slot::from(slots_per_historical_root);
+        state_b.slot = Slot::from(slots_per_historical_root * 2);
+
+        let mut hashes = (0..).into_iter().map(|i| Hash256::from(i));
+
+        for root in &mut state_a.latest_state_roots[..] {
+            *root = hashes.next().unwrap()
+        }
+        for root in &mut state_b.latest_state_roots[..] {
+            *root = hashes.next().unwrap()
+        }
+
+        // The state root at slot `slots_per_historical_root` is `state_a`'s own root.
+        let state_a_root = Hash256::from(slots_per_historical_root as u64);
+        state_b.latest_state_roots[0] = state_a_root;
+        store.put(&state_a_root, &state_a).unwrap();
+
+        let iter = ReverseStateRootsIterator::new(store.clone(), &state_b, Slot::from(0u64));
+
+        let collected: Vec<(Hash256, Slot)> = iter.collect();
+
+        // Mirrors `reverse_block_root_iter`: the walk from genesis covers exactly
+        // `2 * slots_per_historical_root` slots, `0..=(state_b.slot - 1)`.
+        let expected_len = 2 * MainnetEthSpec::slots_per_historical_root();
+
+        assert_eq!(collected.len(), expected_len);
+
+        for (i, (hash, slot)) in collected.into_iter().enumerate() {
+            assert_eq!(slot, Slot::from(i as u64), "slot mismatch at {}", i);
+            assert_eq!(hash, Hash256::from(i as u64), "hash mismatch at {}", i);
+        }
+    }
+
+    #[test]
+    fn state_cache_dedups_store_reads() {
+        let store = Arc::new(MemoryStore::open());
+        let slots_per_historical_root = MainnetEthSpec::slots_per_historical_root();
+
+        let mut state_a: BeaconState<MainnetEthSpec> = get_state();
+        let mut state_b: BeaconState<MainnetEthSpec> = get_state();
+
+        state_a.slot = Slot::from(slots_per_historical_root);
+        state_b.slot = Slot::from(slots_per_historical_root * 2);
+
+        let state_a_root = Hash256::from(slots_per_historical_root as u64);
+        state_b.latest_state_roots[0] = state_a_root;
+        store.put(&state_a_root, &state_a).unwrap();
+
+        let iter =
+            BlockRootsIterator::new_with_default_cache(store.clone(), &state_b, state_b.slot);
+
+        assert_eq!(iter.cache_misses(), 0);
+
+        // The first full walk has to read `state_a` from the store once.
+        iter.clone().last();
+        assert_eq!(iter.cache_misses(), 1);
+
+        // A second walk over a clone shares the same underlying cache, so it shouldn't need to
+        // read `state_a` from the store again.
+        iter.clone().last();
+        assert_eq!(iter.cache_misses(), 1);
+    }
+
+    #[test]
+    fn roots_iter() {
+        let store = Arc::new(MemoryStore::open());
+        let slots_per_historical_root = MainnetEthSpec::slots_per_historical_root();
+
+        let mut state_a: BeaconState<MainnetEthSpec> = get_state();
+        let mut state_b: BeaconState<MainnetEthSpec> = get_state();
+
+        state_a.slot = Slot::from(slots_per_historical_root);
+        state_b.slot = Slot::from(slots_per_historical_root * 2);
+
+        let mut hashes = (0..).into_iter().map(|i| Hash256::from(i));
+
+        for root in &mut state_a.latest_block_roots[..] {
+            *root = hashes.next().unwrap()
+        }
+        for root in &mut state_b.latest_block_roots[..] {
+            *root = hashes.next().unwrap()
+        }
+
+        for slot in 0..slots_per_historical_root {
+            state_a
+                .set_state_root(Slot::from(slot), hashes.next().unwrap())
+                .expect(&format!("should set state_a slot {}", slot));
+        }
+        for slot in slots_per_historical_root..slots_per_historical_root * 2 {
+            state_b
+                .set_state_root(Slot::from(slot), hashes.next().unwrap())
+                .expect(&format!("should set state_b slot {}", slot));
+        }
+
+        let state_a_root = Hash256::from(u64::max_value());
+        state_b.latest_state_roots[0] = state_a_root;
+        store.put(&state_a_root, &state_a).unwrap();
+
+        let iter = RootsIterator::new(store.clone(), &state_b, state_b.slot - 1);
+
+        let mut collected: Vec<(Hash256, Hash256, Slot)> = iter.collect();
+        collected.reverse();
+
+        let expected_len = 2 * MainnetEthSpec::slots_per_historical_root() - 1;
+
+        assert_eq!(collected.len(), expected_len);
+
+        for (i, (block_root, state_root, slot)) in collected.into_iter().enumerate() {
+            assert_eq!(slot, Slot::from(i as u64), "slot mismatch at {}", i);
+            assert_eq!(
+                block_root,
+                Hash256::from(i as u64),
+                "block root mismatch at {}",
+                i
+            );
+
+            let expected_state_root = if i < slots_per_historical_root as usize {
+                state_a.get_state_root(Slot::from(i as u64)).unwrap()
+            } else {
+                state_b.get_state_root(Slot::from(i as u64)).unwrap()
+            };
+            assert_eq!(
+                state_root, *expected_state_root,
+                "state root mismatch at {}",
+                i
+            );
+        }
+    }
 }
@@ -0,0 +1,20 @@
+use crate::path::Path;
+use crate::NodeIndex;
+
+/// Error variants that can occur while building, reading, or verifying a `Partial`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// An empty `path` was supplied where at least one element is required.
+    EmptyPath(),
+    /// The chunk at `NodeIndex` has not yet been loaded into the cache.
+    ChunkNotLoaded(NodeIndex),
+    /// `path` does not resolve to a node in `T`'s merkle tree overlay.
+    InvalidPath(Path),
+    /// A `SerializedPartial`, once reconstructed, does not authenticate against the expected
+    /// root.
+    InvalidRoot(),
+    /// The supplied bytes (first) do not match the size of the field being set (second).
+    InvalidByteLength(usize, usize),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
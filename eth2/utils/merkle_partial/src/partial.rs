@@ -41,6 +41,27 @@ impl<T: MerkleTreeOverlay> Partial<T> {
         Ok(())
     }
 
+    /// As per `load_partial`, but also reconstructs the merkle tree from the loaded chunks and
+    /// rejects the partial if the resulting root doesn't match `expected_root`.
+    ///
+    /// This guards against a peer-supplied `SerializedPartial` silently populating the cache with
+    /// chunks that don't actually authenticate against a known root.
+    pub fn load_partial_verified(
+        &mut self,
+        partial: SerializedPartial,
+        expected_root: Vec<u8>,
+    ) -> Result<()> {
+        self.load_partial(partial)?;
+        self.fill()?;
+        self.refresh()?;
+
+        if self.root() != Some(&expected_root) {
+            return Err(Error::InvalidRoot());
+        }
+
+        Ok(())
+    }
+
     /// Generates a `SerializedPartial` proving that `path` is a part of the current merkle tree.
     pub fn extract_partial(&self, path: Vec<Path>) -> Result<SerializedPartial> {
         if path.len() == 0 {
@@ -78,6 +99,63 @@ impl<T: MerkleTreeOverlay> Partial<T> {
         Ok(SerializedPartial { indices, chunks })
     }
 
+    /// Generates a single `SerializedPartial` proving that every path in `paths` is part of the
+    /// current merkle tree.
+    ///
+    /// This is equivalent to calling `extract_partial` once per path and merging the results, but
+    /// shares authentication nodes between paths instead of duplicating them, which keeps the
+    /// proof minimal when several leaves are requested at once (e.g. several fields of the same
+    /// `BeaconState`).
+    pub fn extract_partial_multiple(&self, paths: Vec<Vec<Path>>) -> Result<SerializedPartial> {
+        if paths.len() == 0 {
+            return Err(Error::EmptyPath());
+        }
+
+        let mut indices: Vec<NodeIndex> = vec![];
+        let mut chunks: Vec<u8> = vec![];
+
+        for path in paths {
+            if path.len() == 0 {
+                return Err(Error::EmptyPath());
+            }
+
+            let node = T::get_node(path.clone())?;
+
+            let mut visitor = node.get_index();
+
+            if !indices.contains(&visitor) {
+                indices.push(visitor);
+                chunks.extend(
+                    self.cache
+                        .get(visitor)
+                        .ok_or(Error::ChunkNotLoaded(visitor))?,
+                );
+            }
+
+            while visitor > 0 {
+                let sibling = sibling_index(visitor);
+                let left = 2 * sibling + 1;
+                let right = 2 * sibling + 2;
+
+                if !indices.contains(&sibling)
+                    && !(indices.contains(&left) && indices.contains(&right))
+                {
+                    indices.push(sibling);
+                    chunks.extend(
+                        self.cache
+                            .get(sibling)
+                            .ok_or(Error::ChunkNotLoaded(sibling))?,
+                    );
+                }
+
+                // visitor /= 2, when 1 indexed
+                visitor = (visitor + 1) / 2 - 1;
+            }
+        }
+
+        Ok(SerializedPartial { indices, chunks })
+    }
+
     /// Returns the bytes representation of the object associated with `path`
     pub fn get_bytes(&self, path: Vec<Path>) -> Result<Vec<u8>> {
         if path.len() == 0 {
@@ -90,6 +168,16 @@ impl<T: MerkleTreeOverlay> Partial<T> {
     }
 
     /// Replaces the bytes at `path` with `bytes`.
+    ///
+    /// `bytes` must be exactly as long as the field at `path` (`end - begin`), which holds for
+    /// any primitive field or list/vector element, not just the 32-byte and 8-byte cases. If the
+    /// backing chunk hasn't been loaded yet (e.g. it's a new slot created by `set_length`
+    /// growing a list), it is initialized to all zeroes first.
+    ///
+    /// This only touches the element's own chunk; it does not update the list/vector's `Length`
+    /// node. When growing or shrinking a list, call `set_length` as well (in either order, so
+    /// long as both complete before the next `refresh`) — a grown list whose `Length` node was
+    /// never updated will `refresh` to the wrong root.
     pub fn set_bytes(&mut self, path: Vec<Path>, bytes: Vec<u8>) -> Result<()> {
         if path.len() == 0 {
             return Err(Error::EmptyPath());
@@ -97,33 +185,48 @@ impl<T: MerkleTreeOverlay> Partial<T> {
 
         let (index, begin, end) = bytes_at_path_helper::<T>(path)?;
 
-        if bytes.len() == 32 {
+        if bytes.len() != end - begin {
+            return Err(Error::InvalidByteLength(bytes.len(), end - begin));
+        }
+
+        if begin == 0 && end == BYTES_PER_CHUNK {
             self.cache.insert(index, bytes);
         } else {
-            // the timestamp is 8 bytes. this stuff below pads it to 32 before inserting
-            let chunk = self
+            let mut chunk = self
                 .cache
                 .get(index)
-                .ok_or(Error::ChunkNotLoaded(index))?
-                .to_vec()
-                .iter()
                 .cloned()
-                .enumerate()
-                .map(|(i, b)| {
-                    if i >= begin && i < end {
-                        bytes[i - begin]
-                    } else {
-                        b
-                    }
-                })
-                .collect();
-            println!("set_bytes inserting chunk: {:?}", chunk);
+                .unwrap_or_else(|| vec![0; BYTES_PER_CHUNK]);
+
+            chunk[begin..end].clone_from_slice(&bytes);
+
             self.cache.insert(index, chunk);
         }
 
         Ok(())
     }
 
+    /// Updates the `Length` node associated with the list/vector at `path` to `new_length`,
+    /// re-deriving the stored length so that a subsequent `refresh` produces a root that reflects
+    /// the list's new size.
+    pub fn set_length(&mut self, path: Vec<Path>, new_length: u64) -> Result<()> {
+        if path.len() == 0 {
+            return Err(Error::EmptyPath());
+        }
+
+        match T::get_node(path.clone())? {
+            Node::Length(l) => {
+                let mut chunk = vec![0; BYTES_PER_CHUNK];
+                chunk[0..8].clone_from_slice(&new_length.to_le_bytes());
+
+                self.cache.insert(l.index, chunk);
+
+                Ok(())
+            }
+            _ => Err(Error::InvalidPath(path[0].clone())),
+        }
+    }
+
     /// Determines if the current merkle tree is valid.
     pub fn is_valid(&self, root: Vec<u8>) -> bool {
         self.cache.is_valid(root)
@@ -177,3 +280,177 @@ fn bytes_at_path_helper<T: MerkleTreeOverlay + ?Sized>(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::{LengthNode, PrimitiveNode};
+
+    /// Minimal `MerkleTreeOverlay` fixture used only to satisfy `Partial<T>`'s type parameter;
+    /// neither of the `load_partial_verified` tests that use it calls into `T::get_node`.
+    struct NoFields;
+
+    impl MerkleTreeOverlay for NoFields {
+        fn get_node(_path: Vec<Path>) -> Result<Node> {
+            unimplemented!("not exercised by the load_partial_verified tests")
+        }
+    }
+
+    /// A fixture with 4 packed-leaf fields arranged as a depth-2 tree:
+    ///
+    /// ```text
+    ///         0
+    ///       /   \
+    ///      1     2
+    ///     / \   / \
+    ///    3   4 5   6
+    /// ```
+    ///
+    /// `field(0)..field(3)` resolve to leaves `3..6` respectively.
+    struct FourFields;
+
+    impl MerkleTreeOverlay for FourFields {
+        fn get_node(path: Vec<Path>) -> Result<Node> {
+            let index = match path.get(0) {
+                Some(Path::Index(i)) if *i < 4 => 3 + *i as NodeIndex,
+                _ => return Err(Error::InvalidPath(path[0].clone())),
+            };
+
+            Ok(Node::Primitive(vec![PrimitiveNode {
+                ident: format!("field_{}", index),
+                offset: 0,
+                size: BYTES_PER_CHUNK as u8,
+                index,
+            }]))
+        }
+    }
+
+    /// Builds a `Partial<FourFields>` with all 4 leaves set to distinct chunks and the
+    /// intermediate/root nodes filled in, ready to have proofs extracted from it.
+    fn filled_four_fields() -> Partial<FourFields> {
+        let mut partial = Partial::<FourFields>::new(SerializedPartial {
+            indices: vec![3, 4, 5, 6],
+            chunks: (0..4u8).flat_map(|i| vec![i; BYTES_PER_CHUNK]).collect(),
+        });
+
+        partial.fill().unwrap();
+        partial.refresh().unwrap();
+
+        partial
+    }
+
+    #[test]
+    fn extract_partial_multiple_dedups_shared_sibling() {
+        let partial = filled_four_fields();
+
+        // `field(0)` (leaf 3) and `field(1)` (leaf 4) are siblings of each other, and both walks
+        // to the root pass through node 2 (the other top-level branch). A naive merge of two
+        // independent `extract_partial` calls would include leaf 3, leaf 4, and node 2 twice
+        // each; `extract_partial_multiple` should include each of them only once.
+        let extracted = partial
+            .extract_partial_multiple(vec![vec![Path::Index(0)], vec![Path::Index(1)]])
+            .unwrap();
+
+        assert_eq!(extracted.indices.len(), 3, "siblings should be deduped");
+
+        let mut indices = extracted.indices.clone();
+        indices.sort();
+        assert_eq!(indices, vec![2, 3, 4]);
+
+        // The extracted partial should still reconstruct the original root.
+        let mut reconstructed = Partial::<FourFields>::new(extracted);
+        reconstructed.fill().unwrap();
+        reconstructed.refresh().unwrap();
+
+        assert_eq!(reconstructed.root(), partial.root());
+    }
+
+    /// A fixture with a two-node tree: `index(1)` is the list's packed data (here, small enough
+    /// to fit in a single chunk), and `index(2)` is its `Length` node.
+    struct GrowableList;
+
+    impl MerkleTreeOverlay for GrowableList {
+        fn get_node(path: Vec<Path>) -> Result<Node> {
+            match path.get(0) {
+                Some(Path::Ident(s)) if s == "len" => Ok(Node::Length(LengthNode { index: 2 })),
+                Some(Path::Index(0)) => Ok(Node::Primitive(vec![PrimitiveNode {
+                    ident: "elem0".to_string(),
+                    offset: 0,
+                    size: BYTES_PER_CHUNK as u8,
+                    index: 1,
+                }])),
+                _ => Err(Error::InvalidPath(path[0].clone())),
+            }
+        }
+    }
+
+    fn length_chunk(len: u64) -> Vec<u8> {
+        let mut chunk = vec![0u8; BYTES_PER_CHUNK];
+        chunk[0..8].clone_from_slice(&len.to_le_bytes());
+        chunk
+    }
+
+    #[test]
+    fn set_bytes_and_set_length_on_grown_list_matches_direct_construction() {
+        // Starts out as an empty list: zeroed data chunk, length 0.
+        let mut grown = Partial::<GrowableList>::new(SerializedPartial {
+            indices: vec![1, 2],
+            chunks: [vec![0u8; BYTES_PER_CHUNK], length_chunk(0)].concat(),
+        });
+
+        // Grow it to hold a single element and set that element's bytes.
+        grown
+            .set_length(vec![Path::Ident("len".to_string())], 1)
+            .unwrap();
+        grown
+            .set_bytes(vec![Path::Index(0)], vec![7u8; BYTES_PER_CHUNK])
+            .unwrap();
+        grown.refresh().unwrap();
+
+        // This should produce the same root as a partial built directly in that grown state.
+        let mut reference = Partial::<GrowableList>::new(SerializedPartial {
+            indices: vec![1, 2],
+            chunks: [vec![7u8; BYTES_PER_CHUNK], length_chunk(1)].concat(),
+        });
+        reference.fill().unwrap();
+        reference.refresh().unwrap();
+
+        assert_eq!(grown.root(), reference.root());
+    }
+
+    fn two_leaf_partial(left: u8, right: u8) -> SerializedPartial {
+        SerializedPartial {
+            indices: vec![1, 2],
+            chunks: [vec![left; BYTES_PER_CHUNK], vec![right; BYTES_PER_CHUNK]].concat(),
+        }
+    }
+
+    #[test]
+    fn load_partial_verified_accepts_matching_root() {
+        let mut reference = Partial::<NoFields>::new(two_leaf_partial(1, 2));
+        reference.fill().unwrap();
+        reference.refresh().unwrap();
+        let expected_root = reference.root().unwrap().clone();
+
+        let mut verifier = Partial::<NoFields>::new(two_leaf_partial(1, 2));
+        assert_eq!(
+            verifier.load_partial_verified(two_leaf_partial(1, 2), expected_root),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn load_partial_verified_rejects_tampered_chunk() {
+        let mut reference = Partial::<NoFields>::new(two_leaf_partial(1, 2));
+        reference.fill().unwrap();
+        reference.refresh().unwrap();
+        let expected_root = reference.root().unwrap().clone();
+
+        // Same indices, but the left chunk has been tampered with relative to `reference`.
+        let mut verifier = Partial::<NoFields>::new(two_leaf_partial(0xff, 2));
+        assert_eq!(
+            verifier.load_partial_verified(two_leaf_partial(0xff, 2), expected_root),
+            Err(Error::InvalidRoot())
+        );
+    }
+}